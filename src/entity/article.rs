@@ -0,0 +1,50 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+/// A single article within a season.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Article {
+    pub title: String,
+    /// Path to the markdown source, relative to the season directory.
+    pub file: PathBuf,
+    #[serde(default)]
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default, skip_deserializing)]
+    pub html: String,
+    /// Rendered HTML up to the `<!-- more -->` marker, if the source has
+    /// one. Used by listing templates instead of the full `html`.
+    #[serde(default, skip_deserializing)]
+    pub summary: Option<String>,
+    /// Set from the article's front matter; articles left as drafts are
+    /// dropped after parsing unless drafts were explicitly requested.
+    #[serde(default, skip_deserializing)]
+    pub draft: bool,
+    /// Arbitrary extra front-matter fields, merged in at parse time.
+    #[serde(default, skip_deserializing)]
+    pub extra: HashMap<String, Value>,
+    #[serde(default, skip_deserializing)]
+    pub word_count: usize,
+    /// Estimated minutes to read, rounded and never less than 1.
+    #[serde(default, skip_deserializing)]
+    pub reading_time: u32,
+    /// Non-markdown files in the subdirectory named after `file`'s stem
+    /// (e.g. `my-article/` for `my-article.md`), copied alongside the
+    /// rendered article so relative links (`![](image.png)`) resolve.
+    #[serde(default, skip_deserializing)]
+    pub assets: Vec<PathBuf>,
+}
+
+impl Article {
+    pub fn slug(&self) -> String {
+        self.slug.clone().unwrap_or_else(|| {
+            self.file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        })
+    }
+}
@@ -1,14 +1,30 @@
+mod article;
+mod page;
+mod season;
+mod theme;
+
+pub use article::Article;
+pub use page::Page;
+pub use season::Season;
+pub use theme::Theme;
+
 use anyhow::Result;
-use pulldown_cmark::{html, Options, Parser as MarkdownParser};
+use rayon::prelude::*;
 use serde::Deserialize;
 use std::{fs, path::Path};
 use tera::Context;
 use walkdir::WalkDir;
 
-use crate::{zine::Render, Article, Page, Season, Theme, Zine, ZINE_FILE};
+use crate::{
+    feed,
+    front_matter,
+    markdown::{count_words, render_markdown},
+    zine::Render,
+    Zine, ZINE_FILE,
+};
 
 pub trait Entity {
-    fn parse(&mut self, _source: &Path) -> Result<()> {
+    fn parse(&mut self, _source: &Path, _theme: &Theme) -> Result<()> {
         Ok(())
     }
 
@@ -17,46 +33,60 @@ pub trait Entity {
     }
 }
 
-impl<T: Entity> Entity for Vec<T> {
-    fn parse(&mut self, source: &Path) -> Result<()> {
-        for item in self {
-            item.parse(source)?;
-        }
-        Ok(())
+impl<T: Entity + Send + Sync> Entity for Vec<T> {
+    fn parse(&mut self, source: &Path, theme: &Theme) -> Result<()> {
+        self.par_iter_mut()
+            .try_for_each(|item| item.parse(source, theme))
     }
 
     fn render(&self, render: Context, dest: &Path) -> Result<()> {
-        for item in self {
-            item.render(render.clone(), dest)?;
-        }
-        Ok(())
+        self.par_iter()
+            .try_for_each(|item| item.render(render.clone(), dest))
     }
 }
 
 impl Entity for Zine {
-    fn parse(&mut self, source: &Path) -> Result<()> {
-        self.theme.parse(source)?;
+    fn parse(&mut self, source: &Path, _theme: &Theme) -> Result<()> {
+        let theme = self.theme.clone();
+        self.theme.parse(source, &theme)?;
 
-        self.seasons.parse(source)?;
+        self.seasons.parse(source, &self.theme)?;
         // Sort all seasons by number.
         self.seasons.sort_unstable_by_key(|s| s.number);
 
-        // Parse pages
+        // Walk the page directory first (cheap, sequential), then parse
+        // each page's markdown in parallel since that's where the cost is.
         let page_dir = source.join("pages");
+        let mut page_paths = Vec::new();
         for entry in WalkDir::new(&page_dir) {
             let entry = entry?;
-            let path = entry.path();
-            if path.is_file() {
+            if entry.path().is_file() {
+                page_paths.push(entry.into_path());
+            }
+        }
+
+        let pages = page_paths
+            .par_iter()
+            .map(|path| -> Result<Option<Page>> {
                 let markdown = fs::read_to_string(path)?;
-                let markdown_parser = MarkdownParser::new_ext(&markdown, Options::all());
-                let mut html = String::new();
-                html::push_html(&mut html, markdown_parser);
-                self.pages.push(Page {
+                let (front_matter, body) = front_matter::split(&markdown)?;
+                let front_matter = front_matter.unwrap_or_default();
+                if front_matter.draft && !self.theme.render_drafts {
+                    return Ok(None);
+                }
+
+                let html = render_markdown(body, &self.theme);
+                Ok(Some(Page {
                     html,
                     file_path: path.strip_prefix(&page_dir)?.to_owned(),
-                });
-            }
-        }
+                    title: front_matter.title,
+                    date: front_matter.date,
+                    slug_override: front_matter.slug,
+                    extra: front_matter.extra,
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.pages.extend(pages.into_iter().flatten());
         Ok(())
     }
 
@@ -72,22 +102,26 @@ impl Entity for Zine {
         // Render home page.
         context.insert("seasons", &self.seasons);
         Render::render("index.jinja", &context, dest)?;
+
+        feed::render_feeds(self, dest)?;
         Ok(())
     }
 }
 
 impl Entity for Theme {
-    fn parse(&mut self, source: &Path) -> Result<()> {
+    fn parse(&mut self, source: &Path, _theme: &Theme) -> Result<()> {
         if let Some(footer_template) = self.footer_template.as_ref() {
-            // Read footer tempolate from path to html.
-            self.footer_template = Some(fs::read_to_string(source.join(&footer_template))?);
+            // Read the footer template from its configured path into
+            // HTML, leaving `footer_template` itself (the path) alone so
+            // re-parsing this same `Theme` later still finds the file.
+            self.footer_template_html = Some(fs::read_to_string(source.join(footer_template))?);
         }
         Ok(())
     }
 }
 
 impl Entity for Season {
-    fn parse(&mut self, source: &Path) -> Result<()> {
+    fn parse(&mut self, source: &Path, theme: &Theme) -> Result<()> {
         // Representing a zine.toml file for season.
         #[derive(Debug, Deserialize)]
         struct SeasonFile {
@@ -100,28 +134,86 @@ impl Entity for Season {
         let season_file = toml::from_str::<SeasonFile>(&content)?;
         self.articles = season_file.articles;
 
-        self.articles.parse(&dir)?;
+        self.articles.parse(&dir, theme)?;
+        if !theme.render_drafts {
+            self.articles.retain(|article| !article.draft);
+        }
         Ok(())
     }
 
     fn render(&self, mut context: Context, dest: &Path) -> Result<()> {
         context.insert("season", &self);
-        Render::render("season.jinja", &context, dest.join(&self.slug))?;
+        let season_dest = dest.join(&self.slug);
+        self.articles.render(context.clone(), &season_dest)?;
+        Render::render("season.jinja", &context, season_dest)?;
         Ok(())
     }
 }
 
 impl Entity for Article {
-    fn parse(&mut self, source: &Path) -> Result<()> {
+    fn parse(&mut self, source: &Path, theme: &Theme) -> Result<()> {
         let markdown = fs::read_to_string(&source.join(&self.file))?;
-        let markdown_parser = MarkdownParser::new_ext(&markdown, Options::all());
-        html::push_html(&mut self.html, markdown_parser);
+
+        // Multiple articles' `file` entries live as siblings directly in
+        // the season directory, so scanning that whole directory would
+        // attach every image in the season to every article. Assets are only
+        // ever colocated in a subdirectory named after the article's
+        // markdown file stem (e.g. `my-article/cover.png` next to
+        // `my-article.md`) — an article with no such subdirectory has no
+        // assets.
+        let article_assets_dir = source
+            .join(&self.file)
+            .parent()
+            .unwrap_or(source)
+            .join(self.file.file_stem().unwrap_or_default());
+        self.assets = if article_assets_dir.is_dir() {
+            fs::read_dir(&article_assets_dir)?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.is_file() && path.extension().map_or(true, |ext| ext != "md")
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let (front_matter, body) = front_matter::split(&markdown)?;
+        if let Some(front_matter) = front_matter {
+            // Front matter values override whatever `zine.toml` declared.
+            if let Some(title) = front_matter.title {
+                self.title = title;
+            }
+            if front_matter.date.is_some() {
+                self.date = front_matter.date;
+            }
+            if front_matter.slug.is_some() {
+                self.slug = front_matter.slug;
+            }
+            self.draft = front_matter.draft;
+            self.extra.extend(front_matter.extra);
+        }
+        self.html = render_markdown(body, theme);
+        self.summary = body
+            .split_once("<!-- more -->")
+            .map(|(before, _)| render_markdown(before, theme));
+
+        self.word_count = count_words(body);
+        self.reading_time = ((self.word_count as f32 / theme.words_per_minute as f32).round()
+            as u32)
+            .max(1);
         Ok(())
     }
 
     fn render(&self, mut context: Context, dest: &Path) -> Result<()> {
         context.insert("article", &self);
-        Render::render("article.jinja", &context, dest)?;
+        let article_dest = dest.join(self.slug());
+        Render::render("article.jinja", &context, &article_dest)?;
+
+        for asset in &self.assets {
+            if let Some(file_name) = asset.file_name() {
+                fs::copy(asset, article_dest.join(file_name))?;
+            }
+        }
         Ok(())
     }
 }
@@ -129,7 +221,157 @@ impl Entity for Article {
 impl Entity for Page {
     fn render(&self, mut context: Context, dest: &Path) -> Result<()> {
         context.insert("content", &self.html);
+        context.insert("page", &self);
         Render::render("page.jinja", &context, dest.join(self.slug()))?;
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh, empty directory under the OS temp dir, removed first in
+    /// case a previous run left it behind.
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("zine-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn article(file: &str) -> Article {
+        Article {
+            title: "Title".to_owned(),
+            file: file.into(),
+            slug: None,
+            date: None,
+            html: String::new(),
+            summary: None,
+            draft: false,
+            extra: Default::default(),
+            word_count: 0,
+            reading_time: 0,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn reading_time_rounds_and_is_never_zero() {
+        let dir = temp_dir("reading-time");
+        // 200 words at the default 200 words/minute rounds to 1 minute.
+        let words = "word ".repeat(200);
+        fs::write(dir.join("a.md"), &words).unwrap();
+
+        let mut a = article("a.md");
+        a.parse(&dir, &Theme::default()).unwrap();
+        assert_eq!(a.word_count, 200);
+        assert_eq!(a.reading_time, 1);
+
+        // 350 words rounds up to 2 minutes.
+        let words = "word ".repeat(350);
+        fs::write(dir.join("b.md"), &words).unwrap();
+        let mut b = article("b.md");
+        b.parse(&dir, &Theme::default()).unwrap();
+        assert_eq!(b.reading_time, 2);
+
+        // An empty article still reports at least 1 minute.
+        fs::write(dir.join("c.md"), "").unwrap();
+        let mut c = article("c.md");
+        c.parse(&dir, &Theme::default()).unwrap();
+        assert_eq!(c.word_count, 0);
+        assert_eq!(c.reading_time, 1);
+    }
+
+    #[test]
+    fn summary_splits_at_the_more_marker() {
+        let dir = temp_dir("summary-marker");
+        fs::write(dir.join("a.md"), "intro\n\n<!-- more -->\n\nrest of the article").unwrap();
+
+        let mut a = article("a.md");
+        a.parse(&dir, &Theme::default()).unwrap();
+        let summary = a.summary.expect("summary should be set");
+        assert!(summary.contains("intro"));
+        assert!(!summary.contains("rest of the article"));
+        assert!(a.html.contains("rest of the article"));
+    }
+
+    #[test]
+    fn summary_is_none_without_a_more_marker() {
+        let dir = temp_dir("summary-no-marker");
+        fs::write(dir.join("a.md"), "just one paragraph, no marker").unwrap();
+
+        let mut a = article("a.md");
+        a.parse(&dir, &Theme::default()).unwrap();
+        assert!(a.summary.is_none());
+    }
+
+    #[test]
+    fn assets_are_scoped_to_the_articles_own_subdirectory() {
+        let dir = temp_dir("asset-scoping");
+        fs::write(dir.join("one.md"), "one").unwrap();
+        fs::write(dir.join("two.md"), "two").unwrap();
+
+        // `one`'s own colocated asset.
+        fs::create_dir_all(dir.join("one")).unwrap();
+        fs::write(dir.join("one").join("cover.png"), "img").unwrap();
+
+        // An asset that only lives loose in the shared season directory
+        // must NOT be picked up by either article.
+        fs::write(dir.join("stray.png"), "img").unwrap();
+
+        let mut one = article("one.md");
+        one.parse(&dir, &Theme::default()).unwrap();
+        assert_eq!(one.assets, vec![dir.join("one").join("cover.png")]);
+
+        let mut two = article("two.md");
+        two.parse(&dir, &Theme::default()).unwrap();
+        assert!(two.assets.is_empty());
+    }
+
+    /// Minimal `Entity` used to exercise the blanket `Vec<T>` impl without
+    /// touching the filesystem.
+    struct Probe {
+        fail: bool,
+    }
+
+    impl Entity for Probe {
+        fn parse(&mut self, _source: &Path, _theme: &Theme) -> Result<()> {
+            if self.fail {
+                anyhow::bail!("boom");
+            }
+            Ok(())
+        }
+
+        fn render(&self, _context: Context, _dest: &Path) -> Result<()> {
+            if self.fail {
+                anyhow::bail!("boom");
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn vec_parse_surfaces_an_error_from_any_item() {
+        let mut items = vec![
+            Probe { fail: false },
+            Probe { fail: true },
+            Probe { fail: false },
+        ];
+
+        assert!(items.parse(Path::new("."), &Theme::default()).is_err());
+    }
+
+    #[test]
+    fn vec_parse_succeeds_when_every_item_succeeds() {
+        let mut items = vec![Probe { fail: false }, Probe { fail: false }];
+        assert!(items.parse(Path::new("."), &Theme::default()).is_ok());
+    }
+
+    #[test]
+    fn vec_render_surfaces_an_error() {
+        let items = vec![Probe { fail: false }, Probe { fail: true }];
+        let result = items.render(Context::new(), Path::new("."));
+        assert!(result.is_err());
+    }
 }
\ No newline at end of file
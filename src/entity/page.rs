@@ -0,0 +1,30 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::Serialize;
+use toml::Value;
+
+/// A standalone page rendered from a markdown file under `pages/`.
+#[derive(Debug, Default, Serialize)]
+pub struct Page {
+    pub html: String,
+    /// Path of the source file, relative to the `pages` directory.
+    pub file_path: PathBuf,
+    /// Title, date and slug overrides from the page's front matter, if
+    /// any.
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub slug_override: Option<String>,
+    /// Arbitrary extra front-matter fields.
+    pub extra: HashMap<String, Value>,
+}
+
+impl Page {
+    pub fn slug(&self) -> String {
+        self.slug_override.clone().unwrap_or_else(|| {
+            self.file_path
+                .with_extension("")
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+}
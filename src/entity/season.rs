@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Article;
+
+/// A season groups a set of articles under a shared slug, e.g. `season/1`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Season {
+    pub number: u32,
+    pub slug: String,
+    /// Directory of this season, relative to the zine source root.
+    pub path: PathBuf,
+    #[serde(default, skip_deserializing)]
+    pub articles: Vec<Article>,
+}
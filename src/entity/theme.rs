@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Rendering and presentation options shared across the whole zine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Path to the footer template file, relative to the zine source
+    /// root, as configured in `zine.toml`. `Entity::parse` never
+    /// mutates this — it reads the file's contents into
+    /// `footer_template_html` instead, so re-parsing the same `Theme`
+    /// (e.g. on every dev-server rebuild) keeps working.
+    pub footer_template: Option<String>,
+    /// Contents of the `footer_template` file, read as-is (not rendered)
+    /// by `Entity::parse`.
+    #[serde(skip)]
+    pub footer_template_html: Option<String>,
+    /// Name of the `syntect` theme used to highlight fenced code blocks,
+    /// e.g. `"InspiredGitHub"` or `"base16-ocean.dark"`.
+    pub syntax_theme: String,
+    /// Whether entities marked `draft = true` in their front matter should
+    /// still be parsed and rendered. Never read from `zine.toml`; it is
+    /// `false` unless set on the `Theme` directly, and nothing in this
+    /// crate sets it.
+    #[serde(skip)]
+    pub render_drafts: bool,
+    /// Average reading speed used to derive `Article::reading_time`.
+    pub words_per_minute: u32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            footer_template: None,
+            footer_template_html: None,
+            syntax_theme: "InspiredGitHub".to_owned(),
+            render_drafts: false,
+            words_per_minute: 200,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::{fs, path::Path};
+
+use anyhow::Result;
+
+use crate::{Article, Season, Zine};
+
+/// Generate an Atom feed for the whole zine at `dest/atom.xml`, plus one
+/// per season under each season's slug directory.
+///
+/// Does nothing when `site.base_url` isn't set, since permalinks can't be
+/// built without it.
+pub fn render_feeds(zine: &Zine, dest: &Path) -> Result<()> {
+    let Some(base_url) = zine.site.base_url.as_deref() else {
+        return Ok(());
+    };
+
+    let mut dated: Vec<(&Season, &Article)> = zine
+        .seasons
+        .iter()
+        .flat_map(|season| season.articles.iter().map(move |article| (season, article)))
+        .filter(|(_, article)| article.date.is_some())
+        .collect();
+    dated.sort_unstable_by(|(_, a), (_, b)| b.date.cmp(&a.date));
+    dated.truncate(zine.site.feed_limit);
+
+    render_atom(base_url, &zine.site.name, "", &dated, dest)?;
+
+    for season in &zine.seasons {
+        // Sort and truncate this season's own articles independently of
+        // the global feed, so an older season isn't starved just because
+        // another season has more recent articles than `feed_limit`.
+        let mut season_entries: Vec<_> = season
+            .articles
+            .iter()
+            .filter(|article| article.date.is_some())
+            .map(|article| (season, article))
+            .collect();
+        season_entries.sort_unstable_by(|(_, a), (_, b)| b.date.cmp(&a.date));
+        season_entries.truncate(zine.site.feed_limit);
+
+        render_atom(
+            base_url,
+            &format!("{} - {}", zine.site.name, season.slug),
+            &season.slug,
+            &season_entries,
+            &dest.join(&season.slug),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn render_atom(
+    base_url: &str,
+    title: &str,
+    feed_path_prefix: &str,
+    entries: &[(&Season, &Article)],
+    dest: &Path,
+) -> Result<()> {
+    // Each feed (global or per-season) needs its own unique `<id>`/self
+    // link, pointing at the file this call is actually writing.
+    let feed_id = if feed_path_prefix.is_empty() {
+        format!("{base_url}/atom.xml")
+    } else {
+        format!("{base_url}/{feed_path_prefix}/atom.xml")
+    };
+
+    let mut xml_entries = String::new();
+    for (season, article) in entries {
+        let permalink = format!("{base_url}/{}/{}", season.slug, article.slug());
+        let date = article.date.as_deref().unwrap_or_default();
+        let content = article.summary.as_deref().unwrap_or(&article.html);
+
+        xml_entries.push_str(&format!(
+            "  <entry>\n    <title>{}</title>\n    <link href=\"{permalink}\"/>\n    <id>{permalink}</id>\n    <updated>{date}</updated>\n    <content type=\"html\"><![CDATA[{content}]]></content>\n  </entry>\n",
+            escape(&article.title),
+        ));
+    }
+
+    let feed = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n  <title>{}</title>\n  <id>{feed_id}</id>\n  <link href=\"{feed_id}\" rel=\"self\"/>\n{xml_entries}</feed>\n",
+        escape(title),
+    );
+
+    fs::create_dir_all(dest)?;
+    fs::write(dest.join("atom.xml"), feed)?;
+    Ok(())
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Zine;
+
+    fn article(title: &str, slug: &str, date: &str) -> Article {
+        Article {
+            title: title.to_owned(),
+            file: format!("{slug}.md").into(),
+            slug: Some(slug.to_owned()),
+            date: Some(date.to_owned()),
+            html: format!("<p>{title}</p>"),
+            summary: None,
+            draft: false,
+            extra: Default::default(),
+            word_count: 0,
+            reading_time: 1,
+            assets: Vec::new(),
+        }
+    }
+
+    fn season(number: u32, slug: &str, articles: Vec<Article>) -> Season {
+        Season {
+            number,
+            slug: slug.to_owned(),
+            path: slug.into(),
+            articles,
+        }
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("zine-feed-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn skips_feed_generation_without_a_base_url() {
+        let dest = temp_dir("no-base-url");
+        let mut zine = Zine::default();
+        zine.seasons = vec![season(1, "season-1", vec![article("A", "a", "2024-01-01")])];
+
+        render_feeds(&zine, &dest).unwrap();
+        assert!(!dest.join("atom.xml").exists());
+    }
+
+    #[test]
+    fn global_and_per_season_feeds_have_distinct_ids_and_permalinks() {
+        let dest = temp_dir("distinct-ids");
+        let mut zine = Zine::default();
+        zine.site.base_url = Some("https://example.com".to_owned());
+        zine.seasons = vec![
+            season(1, "season-1", vec![article("First", "first", "2024-01-01")]),
+            season(2, "season-2", vec![article("Second", "second", "2024-02-01")]),
+        ];
+
+        render_feeds(&zine, &dest).unwrap();
+
+        let global = fs::read_to_string(dest.join("atom.xml")).unwrap();
+        let season_1 = fs::read_to_string(dest.join("season-1/atom.xml")).unwrap();
+        let season_2 = fs::read_to_string(dest.join("season-2/atom.xml")).unwrap();
+
+        assert!(global.contains("<id>https://example.com/atom.xml</id>"));
+        assert!(season_1.contains("<id>https://example.com/season-1/atom.xml</id>"));
+        assert!(season_2.contains("<id>https://example.com/season-2/atom.xml</id>"));
+
+        // The global feed carries both articles; each season feed only
+        // its own.
+        assert!(global.contains("https://example.com/season-1/first"));
+        assert!(global.contains("https://example.com/season-2/second"));
+        assert!(season_1.contains("https://example.com/season-1/first"));
+        assert!(!season_1.contains("second"));
+        assert!(season_2.contains("https://example.com/season-2/second"));
+        assert!(!season_2.contains("first"));
+    }
+
+    #[test]
+    fn undated_articles_are_excluded_from_feeds() {
+        let dest = temp_dir("undated-excluded");
+        let mut zine = Zine::default();
+        zine.site.base_url = Some("https://example.com".to_owned());
+        let mut undated = article("Undated", "undated", "unused");
+        undated.date = None;
+        zine.seasons = vec![season(
+            1,
+            "season-1",
+            vec![article("Dated", "dated", "2024-01-01"), undated],
+        )];
+
+        render_feeds(&zine, &dest).unwrap();
+        let feed = fs::read_to_string(dest.join("atom.xml")).unwrap();
+        assert!(feed.contains("dated"));
+        assert!(!feed.contains("undated"));
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::Deserialize;
+use toml::Value;
+
+const DELIMITER: &str = "+++";
+
+/// Optional per-file metadata that can be placed at the top of a markdown
+/// source, delimited by a pair of `+++` lines, e.g.:
+///
+/// ```text
+/// +++
+/// title = "Hello"
+/// draft = true
+/// +++
+/// # Hello
+/// ```
+///
+/// Fields set here take precedence over the matching fields declared for
+/// the same entity in `zine.toml`.
+#[derive(Debug, Default, Deserialize)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Split optional front matter off the top of `content`.
+///
+/// Returns `(None, content)` unchanged whenever `content` doesn't start
+/// with the `+++` delimiter or is missing the closing one. Delimiter
+/// lines are matched with or without a trailing `\r`, so CRLF files work
+/// the same as LF ones.
+pub fn split(content: &str) -> Result<(Option<FrontMatter>, &str)> {
+    let mut lines = content.split_inclusive('\n');
+    let Some(first_line) = lines.next() else {
+        return Ok((None, content));
+    };
+    if first_line.trim_end_matches(['\r', '\n']) != DELIMITER {
+        return Ok((None, content));
+    }
+
+    let mut pos = first_line.len();
+    for line in lines {
+        if line.trim_end_matches(['\r', '\n']) == DELIMITER {
+            let raw_front_matter = &content[first_line.len()..pos];
+            let front_matter = toml::from_str(raw_front_matter)?;
+            let body = &content[pos + line.len()..];
+            return Ok((Some(front_matter), body));
+        }
+        pos += line.len();
+    }
+
+    Ok((None, content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_front_matter_passes_content_through_unchanged() {
+        let content = "# Just a heading\n\nbody text\n";
+        let (front_matter, body) = split(content).unwrap();
+        assert!(front_matter.is_none());
+        assert_eq!(body, content);
+    }
+
+    #[test]
+    fn splits_title_date_slug_draft_and_extra() {
+        let content = "+++\ntitle = \"Hello\"\ndate = \"2024-01-01\"\nslug = \"hello\"\ndraft = true\ntags = [\"a\", \"b\"]\n+++\n# Hello\n";
+        let (front_matter, body) = split(content).unwrap();
+        let front_matter = front_matter.unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Hello"));
+        assert_eq!(front_matter.date.as_deref(), Some("2024-01-01"));
+        assert_eq!(front_matter.slug.as_deref(), Some("hello"));
+        assert!(front_matter.draft);
+        assert!(front_matter.extra.contains_key("tags"));
+        assert_eq!(body, "# Hello\n");
+    }
+
+    #[test]
+    fn missing_closing_delimiter_passes_content_through_unchanged() {
+        let content = "+++\ntitle = \"Hello\"\n# Hello\n";
+        let (front_matter, body) = split(content).unwrap();
+        assert!(front_matter.is_none());
+        assert_eq!(body, content);
+    }
+
+    #[test]
+    fn crlf_line_endings_are_supported() {
+        let content = "+++\r\ntitle = \"Hello\"\r\n+++\r\nbody\r\n";
+        let (front_matter, body) = split(content).unwrap();
+        assert_eq!(front_matter.unwrap().title.as_deref(), Some("Hello"));
+        assert_eq!(body, "body\r\n");
+    }
+}
@@ -0,0 +1,15 @@
+mod entity;
+mod feed;
+mod front_matter;
+mod markdown;
+mod serve;
+mod zine;
+
+pub use entity::Entity;
+pub use serve::serve;
+pub use zine::{Render, Zine};
+
+pub(crate) use entity::{Article, Page, Season, Theme};
+
+/// The well-known file name that describes a zine or a season.
+pub(crate) const ZINE_FILE: &str = "zine.toml";
@@ -0,0 +1,135 @@
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, CodeBlockKind, Event, Options, Parser as MarkdownParser, Tag};
+use syntect::{
+    highlighting::ThemeSet, html::highlighted_html_for_string, parsing::SyntaxSet,
+};
+
+use crate::Theme;
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// Render a markdown source string to HTML, highlighting fenced code blocks
+/// with `syntect` using the theme named on [`Theme::syntax_theme`].
+///
+/// This is the single entry point both `Article::parse` and `Zine::parse`
+/// (for standalone pages) should use, so the two parse sites can't drift.
+pub fn render_markdown(markdown: &str, theme: &Theme) -> String {
+    let syntect_theme = THEME_SET
+        .themes
+        .get(theme.syntax_theme.as_str())
+        .unwrap_or(&THEME_SET.themes["InspiredGitHub"]);
+
+    let parser = MarkdownParser::new_ext(markdown, Options::all());
+    let mut events = Vec::new();
+    // `code_block_lang` being `Some` is also what tracks "we're inside a
+    // fenced block" — an indented code block never sets it, so its
+    // `Start`/`End` pass through to `other` untouched.
+    let mut code_block_lang: Option<String> = None;
+    let mut code_buf = String::new();
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => {
+                code_block_lang = Some(lang.to_string());
+                code_buf.clear();
+            }
+            Event::Text(text) if code_block_lang.is_some() => {
+                code_buf.push_str(&text);
+            }
+            Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(_))) if code_block_lang.is_some() => {
+                let lang = code_block_lang.take().unwrap_or_default();
+                let html = highlight_code_block(&lang, &code_buf, syntect_theme);
+                events.push(Event::Html(html.into()));
+                code_buf.clear();
+            }
+            other => events.push(other),
+        }
+    }
+
+    let mut html = String::new();
+    html::push_html(&mut html, events.into_iter());
+    html
+}
+
+fn highlight_code_block(lang: &str, code: &str, theme: &syntect::highlighting::Theme) -> String {
+    let syntax = lang
+        .split_whitespace()
+        .next()
+        .filter(|lang| !lang.is_empty())
+        .and_then(|lang| SYNTAX_SET.find_syntax_by_token(lang));
+
+    match syntax {
+        Some(syntax) => highlighted_html_for_string(code, &SYNTAX_SET, syntax, theme)
+            .unwrap_or_else(|_| escape_code_block(code)),
+        None => escape_code_block(code),
+    }
+}
+
+fn escape_code_block(code: &str) -> String {
+    let mut escaped = String::new();
+    html::push_html(
+        &mut escaped,
+        std::iter::once(Event::Text(code.into())),
+    );
+    format!("<pre><code>{escaped}</code></pre>\n")
+}
+
+/// Count the words in a markdown source, skipping fenced code blocks, for
+/// reading-time estimation.
+pub fn count_words(markdown: &str) -> usize {
+    let parser = MarkdownParser::new_ext(markdown, Options::all());
+    let mut in_code_block = false;
+    let mut word_count = 0;
+
+    for event in parser {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            Event::Text(text) if !in_code_block => {
+                word_count += text.split_whitespace().count();
+            }
+            _ => {}
+        }
+    }
+
+    word_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fenced_code_block_is_highlighted_and_closed() {
+        let theme = Theme::default();
+        let html = render_markdown("```rust\nfn main() {}\n```\n", &theme);
+        assert_eq!(html.matches("<pre").count(), 1);
+        assert_eq!(html.matches("</pre>").count(), 1);
+    }
+
+    #[test]
+    fn indented_code_block_is_left_untouched() {
+        let theme = Theme::default();
+        let html = render_markdown("paragraph\n\n    indented code\n", &theme);
+        assert_eq!(html.matches("<pre").count(), 1);
+        assert_eq!(html.matches("</pre>").count(), 1);
+        assert!(html.contains("indented code"));
+    }
+
+    #[test]
+    fn unknown_language_falls_back_to_escaped_plain_block() {
+        let theme = Theme::default();
+        let html = render_markdown("```not-a-real-lang\n<b>raw</b>\n```\n", &theme);
+        assert!(html.contains("&lt;b&gt;raw&lt;/b&gt;"));
+    }
+
+    #[test]
+    fn count_words_ignores_fenced_code_blocks() {
+        assert_eq!(count_words("one two three"), 3);
+        assert_eq!(
+            count_words("one two\n\n```rust\nlet x = 1;\nlet y = 2;\n```\n"),
+            2
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::Result;
+use notify::{RecursiveMode, Watcher};
+use tera::Context;
+use tiny_http::{Header, Response, Server};
+
+use crate::{Entity, Theme, Zine};
+
+/// Script injected into every served page; polls for a build generation
+/// newer than the one the page was served at, and reloads the tab once
+/// one shows up. `served_generation` must be the build generation in
+/// effect when the page was rendered, or the client reloads in a loop
+/// the instant it sees any later rebuild.
+fn reload_script(served_generation: u64) -> String {
+    format!(
+        r#"<script>
+(function poll(seen) {{
+  fetch("/__zine/reload?since=" + seen)
+    .then((res) => res.json())
+    .then((gen) => {{ if (gen !== seen) location.reload(); else poll(gen); }})
+    .catch(() => setTimeout(() => poll(seen), 1000));
+}})({served_generation});
+</script>"#
+    )
+}
+
+/// Build `zine` once, then serve `dest` over HTTP while watching `source`
+/// for changes, rebuilding and live-reloading the browser on each one.
+pub fn serve(source: &Path, dest: &Path, addr: &str, mut zine: Zine) -> Result<()> {
+    build(&mut zine, source, dest)?;
+
+    let generation = Arc::new(AtomicU64::new(0));
+    spawn_http_server(addr, dest, generation.clone())?;
+    watch(source, dest, zine, generation)
+}
+
+fn build(zine: &mut Zine, source: &Path, dest: &Path) -> Result<()> {
+    zine.pages.clear();
+    zine.parse(source, &Theme::default())?;
+    zine.render(Context::new(), dest)
+}
+
+fn spawn_http_server(addr: &str, dest: &Path, generation: Arc<AtomicU64>) -> Result<()> {
+    let server = Server::http(addr).map_err(|err| anyhow::anyhow!("failed to bind {addr}: {err}"))?;
+    let dest = dest.to_owned();
+
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let response = if request.url().starts_with("/__zine/reload") {
+                let body = generation.load(Ordering::SeqCst).to_string();
+                Response::from_string(body)
+            } else {
+                serve_static_file(&dest, request.url(), generation.load(Ordering::SeqCst))
+            };
+            let _ = request.respond(response);
+        }
+    });
+    Ok(())
+}
+
+fn serve_static_file(dest: &Path, url: &str, generation: u64) -> Response<std::io::Cursor<Vec<u8>>> {
+    let Some(path) = resolve_dest_path(dest, url) else {
+        return Response::from_string("403 Forbidden").with_status_code(403);
+    };
+
+    let Ok(bytes) = std::fs::read(&path) else {
+        return Response::from_string("404 Not Found").with_status_code(404);
+    };
+    if path.extension().map_or(false, |ext| ext == "html") {
+        let mut html = String::from_utf8_lossy(&bytes).into_owned();
+        html.push_str(&reload_script(generation));
+        let content_type =
+            Header::from_bytes(&b"Content-Type"[..], &b"text/html; charset=UTF-8"[..]).unwrap();
+        Response::from_string(html).with_header(content_type)
+    } else {
+        Response::from_data(bytes)
+    }
+}
+
+/// Resolve a request URL to a path under `dest`, refusing anything that
+/// would climb out of it via `..` (or any other non-literal component).
+fn resolve_dest_path(dest: &Path, url: &str) -> Option<PathBuf> {
+    let relative = url.split('?').next().unwrap_or(url).trim_start_matches('/');
+
+    let mut resolved = dest.to_owned();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    if resolved.is_dir() {
+        resolved = resolved.join("index.html");
+    }
+    Some(resolved)
+}
+
+/// Watch everything that can affect the rendered output: standalone
+/// pages, each season directory, the theme footer template and every
+/// `zine.toml`. Debounces bursts of filesystem events (e.g. editors
+/// that write-then-rename) before triggering a rebuild.
+fn watch(source: &Path, dest: &Path, mut zine: Zine, generation: Arc<AtomicU64>) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+
+    watcher.watch(&source.join("pages"), RecursiveMode::Recursive)?;
+    watcher.watch(&source.join(crate::ZINE_FILE), RecursiveMode::NonRecursive)?;
+    for season in &zine.seasons {
+        watcher.watch(&source.join(&season.path), RecursiveMode::Recursive)?;
+    }
+    if let Some(footer_template) = zine.theme.footer_template.as_ref() {
+        watcher.watch(&source.join(footer_template), RecursiveMode::NonRecursive)?;
+    }
+
+    while rx.recv().is_ok() {
+        // Drain any further events from the same burst before rebuilding.
+        std::thread::sleep(Duration::from_millis(100));
+        while rx.try_recv().is_ok() {}
+
+        match build(&mut zine, source, dest) {
+            Ok(()) => {
+                generation.fetch_add(1, Ordering::SeqCst);
+            }
+            Err(err) => eprintln!("zine: rebuild failed: {err:#}"),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("zine-serve-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolves_a_normal_path_under_dest() {
+        let dest = temp_dir("normal-path");
+        std::fs::write(dest.join("a.html"), "hi").unwrap();
+        assert_eq!(resolve_dest_path(&dest, "/a.html"), Some(dest.join("a.html")));
+    }
+
+    #[test]
+    fn resolves_a_directory_request_to_its_index_html() {
+        let dest = temp_dir("directory-index");
+        std::fs::create_dir_all(dest.join("season-1")).unwrap();
+        assert_eq!(
+            resolve_dest_path(&dest, "/season-1"),
+            Some(dest.join("season-1").join("index.html")),
+        );
+    }
+
+    #[test]
+    fn rejects_parent_dir_components() {
+        let dest = temp_dir("parent-dir");
+        assert_eq!(resolve_dest_path(&dest, "/../../src/lib.rs"), None);
+        assert_eq!(resolve_dest_path(&dest, "/pages/../../secret"), None);
+    }
+
+    #[test]
+    fn strips_the_query_string_before_resolving() {
+        let dest = temp_dir("query-string");
+        std::fs::write(dest.join("a.html"), "hi").unwrap();
+        assert_eq!(
+            resolve_dest_path(&dest, "/a.html?since=3"),
+            Some(dest.join("a.html")),
+        );
+    }
+
+    #[test]
+    fn reload_script_embeds_the_served_generation() {
+        let script = reload_script(7);
+        assert!(script.contains("poll(7)"));
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tera::{Context, Tera};
+
+use crate::{Page, Season, Theme};
+
+static TEMPLATES: Lazy<Tera> =
+    Lazy::new(|| Tera::new("templates/**/*.jinja").expect("failed to load templates"));
+
+/// Site-wide metadata, mirrored from the root `zine.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Site {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Absolute URL the site is served from, e.g. `https://example.com`.
+    /// Required for feed generation; feeds are skipped without it.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Maximum number of articles to include in a generated feed.
+    #[serde(default = "default_feed_limit")]
+    pub feed_limit: usize,
+}
+
+impl Default for Site {
+    fn default() -> Self {
+        Site {
+            name: String::new(),
+            description: None,
+            base_url: None,
+            feed_limit: default_feed_limit(),
+        }
+    }
+}
+
+fn default_feed_limit() -> usize {
+    20
+}
+
+/// The root entity of a zine: seasons, standalone pages and the theme.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Zine {
+    #[serde(default)]
+    pub site: Site,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(rename = "season", default)]
+    pub seasons: Vec<Season>,
+    #[serde(skip)]
+    pub pages: Vec<Page>,
+}
+
+/// Thin wrapper around the shared `Tera` instance used to render a single
+/// template to a file under `dest`.
+pub struct Render;
+
+impl Render {
+    pub fn render(template: &str, context: &Context, dest: impl AsRef<Path>) -> Result<()> {
+        let dest = dest.as_ref();
+        std::fs::create_dir_all(dest)?;
+        let html = TEMPLATES.render(template, context)?;
+        std::fs::write(dest.join("index.html"), html)?;
+        Ok(())
+    }
+}